@@ -0,0 +1,10 @@
+use thiserror::Error as ThisError;
+
+/// An error that occurred while using the SDK.
+#[derive(Debug, ThisError)]
+pub enum Error {
+    /// The sort rule passed to [`Query::with_sort`](crate::search::Query::with_sort) could
+    /// not be parsed into an [`AscDesc`](crate::search::AscDesc).
+    #[error("invalid sort rule `{0}`: expected `<field>:asc` or `<field>:desc`")]
+    InvalidSortRule(String),
+}
@@ -1,25 +1,104 @@
 use crate::{errors::Error, indexes::Index};
-use serde::{de::DeserializeOwned, Deserialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::collections::HashMap;
 use serde_json::to_string;
 
-// TODO support https://docs.meilisearch.com/guides/advanced_guides/search_parameters.html#matches
-// TODO highlighting
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A single sort rule, as accepted by [`Query::with_sort`]: the name of the attribute to
+/// sort by, and the direction to sort it in.
+pub enum AscDesc {
+    /// Sort the given attribute in ascending order, e.g. `price:asc`.
+    Asc(String),
+    /// Sort the given attribute in descending order, e.g. `price:desc`.
+    Desc(String),
+}
+
+impl std::str::FromStr for AscDesc {
+    type Err = Error;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        match rule.rsplit_once(':') {
+            Some((field, "asc")) => Ok(AscDesc::Asc(field.to_string())),
+            Some((field, "desc")) => Ok(AscDesc::Desc(field.to_string())),
+            _ => Err(Error::InvalidSortRule(rule.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for AscDesc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AscDesc::Asc(field) => write!(f, "{}:asc", field),
+            AscDesc::Desc(field) => write!(f, "{}:desc", field),
+        }
+    }
+}
+
+impl Serialize for AscDesc {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// The strategy used to match query terms against documents, set via
+/// [`Query::with_matching_strategy`].
+pub enum MatchingStrategy {
+    /// Remove query words from the end one by one until at least one document matches,
+    /// maximizing recall on long multi-word queries.
+    Last,
+    /// Require every query word to be present in a document for it to match.
+    All,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+/// The byte offset and length of a single matched term within an attribute, as
+/// returned in the `_matchesInfo` field when [`Query::with_matches`] is set.
+pub struct MatchRange {
+    /// The position of the first matched byte in the attribute.
+    pub start: usize,
+    /// The number of matched bytes, starting at `start`.
+    pub length: usize,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+/// A single hit returned by a search query, together with the highlighted/cropped
+/// version of its attributes when [`attributes_to_highlight`](Query::attributes_to_highlight)
+/// or [`attributes_to_crop`](Query::attributes_to_crop) were requested.
+pub struct SearchResult<T> {
+    /// The full document, as stored in the index.
+    #[serde(flatten)]
+    pub result: T,
+    /// The `_formatted` field of the document, i.e. the same document but with the
+    /// matched terms in the requested attributes wrapped in the configured
+    /// [`highlight_pre_tag`](Query::highlight_pre_tag)/[`highlight_post_tag`](Query::highlight_post_tag)
+    /// and cropped attributes reduced to [`crop_length`](Query::crop_length) characters on
+    /// each side of the match.
+    #[serde(rename = "_formatted")]
+    pub formatted_result: Option<T>,
+    /// The `_matchesInfo` field of the document, giving the position of every matched
+    /// term within each attribute requested through [`Query::with_matches`]. Keyed by
+    /// attribute name.
+    #[serde(rename = "_matchesInfo")]
+    pub matches_info: Option<HashMap<String, Vec<MatchRange>>>,
+}
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 /// A struct containing search results and other information about the search.
 pub struct SearchResults<T> {
     /// results of the query
-    pub hits: Vec<T>,
-    /// number of documents skipped
-    pub offset: usize,
-    /// number of documents to take
-    pub limit: usize,
-    /// total number of matches
-    pub nb_hits: usize,
-    /// whether nbHits is exhaustive
-    pub exhaustive_nb_hits: bool,
+    pub hits: Vec<SearchResult<T>>,
+    /// number of documents skipped. Only set when the query used `offset`/`limit`.
+    pub offset: Option<usize>,
+    /// number of documents to take. Only set when the query used `offset`/`limit`.
+    pub limit: Option<usize>,
+    /// total number of matches. Only set when the query used `offset`/`limit`.
+    pub nb_hits: Option<usize>,
+    /// whether nbHits is exhaustive. Only set when the query used `offset`/`limit`.
+    pub exhaustive_nb_hits: Option<bool>,
     /// Distribution of the given facets.
     pub facets_distribution: Option<HashMap<String, HashMap<String, usize>>>,
     /// Whether facet_distribution is exhaustive
@@ -28,8 +107,39 @@ pub struct SearchResults<T> {
     pub processing_time_ms: usize,
     /// query originating the response
     pub query: String,
+    /// the current page number. Only set when the query used [`Query::with_page`].
+    pub page: Option<usize>,
+    /// number of hits returned per page. Only set when the query used [`Query::with_hits_per_page`].
+    pub hits_per_page: Option<usize>,
+    /// total number of pages of results. Only set when the query used page-based pagination.
+    pub total_pages: Option<usize>,
+    /// exact total number of matching documents. Only set when the query used page-based pagination.
+    pub total_hits: Option<usize>,
+    /// estimated total number of matching documents, cheaper to compute than `total_hits`.
+    /// Only set when the query used page-based pagination.
+    pub estimated_total_hits: Option<usize>,
 }
 
+/// Serializes `facets_distribution` the same way [`Query::to_url`] does: `Some(None)` is
+/// the wildcard and is written as the literal string `"*"`, not as `null`. Only called by
+/// `serde` when the outer `Option` is `Some` (see the field's `skip_serializing_if`).
+fn serialize_facets_distribution<S: serde::Serializer>(
+    facets_distribution: &Option<Option<Vec<&str>>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match facets_distribution {
+        Some(Some(facets_distribution)) => facets_distribution.serialize(serializer),
+        Some(None) => serializer.serialize_str("*"),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// The GET querystring length above which [`Query::execute`] automatically switches to
+/// [`Query::execute_post`], since most servers and proxies start rejecting or truncating
+/// URLs well before the 8KB mark. Chosen conservatively enough to leave room for the
+/// request's scheme/host/path alongside the querystring built by [`Query::to_url`].
+const MAX_GET_QUERY_LENGTH: usize = 2000;
+
 /// A struct representing a query.
 /// You can add search parameters using the builder syntax.
 /// See [here](https://docs.meilisearch.com/guides/advanced_guides/search_parameters.html#query-q) for the list and description of all parameters.
@@ -42,39 +152,110 @@ pub struct SearchResults<T> {
 ///     .with_offset(42)
 ///     .with_limit(21);
 /// ```
+///
+/// [`with_sort`](Query::with_sort) validates its rules eagerly, so it returns a `Result`
+/// and breaks the chain with `?` (or a `match`) instead of being fluent like the other
+/// builders:
+///
+/// ```
+/// # use meilisearch_sdk::search::Query;
+/// # fn run() -> Result<(), meilisearch_sdk::errors::Error> {
+/// let query = Query::new("space")
+///     .with_sort(&["price:asc", "release_date:desc"])?
+///     .with_limit(21);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Query<'a> {
     /// The query parameter is the only mandatory parameter.
     /// This is the string used by the search engine to find relevant documents.
+    #[serde(rename = "q")]
     pub query: &'a str,
     /// A number of documents to skip. If the value of the parameter offset is n, n first documents to skip. This is helpful for pagination.
     ///
     /// Example: If you want to skip the first document, set offset to 1.
     /// Default: 0
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub offset: Option<usize>,
     /// Set a limit to the number of documents returned by search queries. If the value of the parameter limit is n, there will be n documents in the search query response. This is helpful for pagination.
     ///
     /// Example: If you want to get only two documents, set limit to 2.
     /// Default: 20
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<usize>,
     /// Specify a filter to be used with the query. See the [dedicated guide](https://docs.meilisearch.com/guides/advanced_guides/filtering.html).
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub filters: Option<&'a str>,
     /// Facet names and values to filter on. See [this page](https://docs.meilisearch.com/guides/advanced_guides/search_parameters.html#facet-filters).
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_filters: Option<Vec<Vec<&'a str>>>,
     /// Facets for which to retrieve the matching count. The value `Some(None)` is the wildcard.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_facets_distribution"
+    )]
     pub facets_distribution: Option<Option<Vec<&'a str>>>,
     /// Attributes to display in the returned documents. Comma-separated list of attributes whose fields will be present in the returned documents.
     ///
     /// Example: If you want to get only the overview and title field and not the other fields, set `attributes_to_retrieve` to `overview,title`.
     /// Default: The [displayed attributes list](https://docs.meilisearch.com/guides/advanced_guides/settings.html#displayed-attributes) which contains by default all attributes found in the documents.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub attributes_to_retrieve: Option<&'a str>,
-    /// TODO [doc](https://docs.meilisearch.com/guides/advanced_guides/search_parameters.html#attributes-to-crop)
+    /// Attributes whose matched terms should be surrounded by `crop_length` characters on
+    /// each side in the returned documents' `_formatted` attributes. Comma-separated list
+    /// of attribute names, e.g. `overview,title`.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub attributes_to_crop: Option<&'a str>,
     /// Number of characters to keep on each side of the start of the matching word. See [attributes_to_crop](#structfield.attributes_to_crop).
     ///
     /// Default: 200
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub crop_length: Option<usize>,
-    /// TODO [doc](https://docs.meilisearch.com/guides/advanced_guides/search_parameters.html#attributes-to-highlight)
+    /// Attributes whose matched terms should be wrapped in `highlight_pre_tag`/
+    /// `highlight_post_tag` in the returned documents' `_formatted` attributes.
+    /// Comma-separated list of attribute names, e.g. `overview,title`.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub attributes_to_highlight: Option<&'a str>,
+    /// The opening tag wrapped around matched terms inside `attributes_to_highlight`.
+    ///
+    /// Default: `<em>`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight_pre_tag: Option<&'a str>,
+    /// The closing tag wrapped around matched terms inside `attributes_to_highlight`.
+    ///
+    /// Default: `</em>`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight_post_tag: Option<&'a str>,
+    /// The marker inserted where `attributes_to_crop` was cropped before or after the match.
+    ///
+    /// Default: `…`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crop_marker: Option<&'a str>,
+    /// Defines whether an object that contains information about the matches should be
+    /// returned or not, under the `_matchesInfo` field of each hit. See [`MatchRange`].
+    ///
+    /// Default: `false`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matches: Option<bool>,
+    /// The page number to retrieve, as an alternative to `offset`/`limit`-based pagination.
+    /// When used, the response carries exact `total_pages`/`total_hits` instead of
+    /// `nb_hits`/`exhaustive_nb_hits`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<usize>,
+    /// The number of hits to return per page, to be used alongside [`page`](Query::page).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hits_per_page: Option<usize>,
+    /// Sort the hits by the given rules, e.g. `"price:asc"`. Applied in order, so the first
+    /// rule that distinguishes two documents wins. See [`Query::with_sort`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<Vec<AscDesc>>,
+    /// The strategy used to match query terms against documents. See [`MatchingStrategy`].
+    ///
+    /// Default: [`MatchingStrategy::Last`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matching_strategy: Option<MatchingStrategy>,
 }
 
 #[allow(missing_docs)]
@@ -91,6 +272,14 @@ impl<'a> Query<'a> {
             attributes_to_crop: None,
             attributes_to_highlight: None,
             crop_length: None,
+            highlight_pre_tag: None,
+            highlight_post_tag: None,
+            crop_marker: None,
+            matches: None,
+            page: None,
+            hits_per_page: None,
+            sort: None,
+            matching_strategy: None,
         }
     }
     pub fn with_offset(self, offset: usize) -> Query<'a> {
@@ -147,6 +336,65 @@ impl<'a> Query<'a> {
             ..self
         }
     }
+    pub fn with_highlight_pre_tag(self, highlight_pre_tag: &'a str) -> Query<'a> {
+        Query {
+            highlight_pre_tag: Some(highlight_pre_tag),
+            ..self
+        }
+    }
+    pub fn with_highlight_post_tag(self, highlight_post_tag: &'a str) -> Query<'a> {
+        Query {
+            highlight_post_tag: Some(highlight_post_tag),
+            ..self
+        }
+    }
+    pub fn with_crop_marker(self, crop_marker: &'a str) -> Query<'a> {
+        Query {
+            crop_marker: Some(crop_marker),
+            ..self
+        }
+    }
+    pub fn with_matches(self, matches: bool) -> Query<'a> {
+        Query {
+            matches: Some(matches),
+            ..self
+        }
+    }
+    pub fn with_page(self, page: usize) -> Query<'a> {
+        Query {
+            page: Some(page),
+            ..self
+        }
+    }
+    pub fn with_hits_per_page(self, hits_per_page: usize) -> Query<'a> {
+        Query {
+            hits_per_page: Some(hits_per_page),
+            ..self
+        }
+    }
+    /// Sort the hits by the given rules, e.g. `&["price:asc", "release_date:desc"]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidSortRule`] if a rule is not of the form `<field>:asc` or
+    /// `<field>:desc`, so that a malformed rule is rejected locally instead of surfacing as
+    /// an opaque server error.
+    pub fn with_sort(self, sort: &[&str]) -> Result<Query<'a>, Error> {
+        let sort = sort
+            .iter()
+            .map(|rule| rule.parse())
+            .collect::<Result<Vec<AscDesc>, Error>>()?;
+        Ok(Query {
+            sort: Some(sort),
+            ..self
+        })
+    }
+    pub fn with_matching_strategy(self, matching_strategy: MatchingStrategy) -> Query<'a> {
+        Query {
+            matching_strategy: Some(matching_strategy),
+            ..self
+        }
+    }
 }
 
 impl<'a> Query<'a> {
@@ -193,15 +441,275 @@ impl<'a> Query<'a> {
             url.push_str("&attributesToHighlight=");
             url.push_str(encode(attributes_to_highlight).as_str());
         }
+        if let Some(highlight_pre_tag) = self.highlight_pre_tag {
+            url.push_str("&highlightPreTag=");
+            url.push_str(encode(highlight_pre_tag).as_str());
+        }
+        if let Some(highlight_post_tag) = self.highlight_post_tag {
+            url.push_str("&highlightPostTag=");
+            url.push_str(encode(highlight_post_tag).as_str());
+        }
+        if let Some(crop_marker) = self.crop_marker {
+            url.push_str("&cropMarker=");
+            url.push_str(encode(crop_marker).as_str());
+        }
+        if let Some(matches) = self.matches {
+            url.push_str("&matches=");
+            url.push_str(matches.to_string().as_str());
+        }
+        if let Some(page) = self.page {
+            url.push_str("&page=");
+            url.push_str(page.to_string().as_str());
+        }
+        if let Some(hits_per_page) = self.hits_per_page {
+            url.push_str("&hitsPerPage=");
+            url.push_str(hits_per_page.to_string().as_str());
+        }
+        if let Some(sort) = &self.sort {
+            url.push_str("&sort=");
+            url.push_str(encode(&to_string(&sort).unwrap()).as_str());
+        }
+        if let Some(matching_strategy) = &self.matching_strategy {
+            url.push_str("&matchingStrategy=");
+            url.push_str(match matching_strategy {
+                MatchingStrategy::Last => "last",
+                MatchingStrategy::All => "all",
+            });
+        }
 
         url
     }
 
-    /// Alias for [the Index method](../indexes/struct.Index.html#method.search).
+    /// Alias for [the Index method](../indexes/struct.Index.html#method.search). Falls back
+    /// to [`execute_post`](Query::execute_post) when the GET querystring built from this
+    /// query would exceed [`MAX_GET_QUERY_LENGTH`], so large `facet_filters`/`filters`
+    /// payloads don't silently hit URL-length limits; smaller queries keep using GET, which
+    /// proxies and browsers can cache.
     pub async fn execute<T: 'static + DeserializeOwned>(
         &'a self,
         index: &Index<'a>,
     ) -> Result<SearchResults<T>, Error> {
+        if self.to_url().len() > MAX_GET_QUERY_LENGTH {
+            return self.execute_post(index).await;
+        }
         index.search::<T>(&self).await
     }
+
+    /// Sends the query as a JSON body to the `POST /indexes/{uid}/search` route instead
+    /// of a GET querystring. Prefer this over [`execute`](Query::execute) whenever
+    /// `facet_filters` or `filters` are large enough to risk hitting URL-length limits or
+    /// percent-encoding edge cases, since the whole query is carried in the request body.
+    ///
+    /// Alias for [the Index method](../indexes/struct.Index.html#method.search_post).
+    pub async fn execute_post<T: 'static + DeserializeOwned>(
+        &'a self,
+        index: &Index<'a>,
+    ) -> Result<SearchResults<T>, Error> {
+        index.search_post::<T>(&self).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use urlencoding::encode;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Movie {
+        title: String,
+    }
+
+    #[test]
+    fn highlight_and_crop_builders_are_encoded_in_the_url() {
+        let query = Query::new("space")
+            .with_attributes_to_highlight("title")
+            .with_highlight_pre_tag("<mark>")
+            .with_highlight_post_tag("</mark>")
+            .with_attributes_to_crop("overview")
+            .with_crop_length(10)
+            .with_crop_marker("[...]");
+        let url = query.to_url();
+        assert!(url.contains("&attributesToHighlight=title"));
+        assert!(url.contains(&format!("&highlightPreTag={}", encode("<mark>"))));
+        assert!(url.contains(&format!("&highlightPostTag={}", encode("</mark>"))));
+        assert!(url.contains("&attributesToCrop=overview"));
+        assert!(url.contains("&cropLength=10"));
+        assert!(url.contains(&format!("&cropMarker={}", encode("[...]"))));
+    }
+
+    #[test]
+    fn search_result_flattens_the_document_and_deserializes_formatted_result() {
+        let hit: SearchResult<Movie> = serde_json::from_str(
+            r#"{"title":"Interstellar","_formatted":{"title":"<em>Inter</em>stellar"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            hit.result,
+            Movie {
+                title: "Interstellar".to_string()
+            }
+        );
+        assert_eq!(
+            hit.formatted_result,
+            Some(Movie {
+                title: "<em>Inter</em>stellar".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn search_result_formatted_result_is_none_when_absent() {
+        let hit: SearchResult<Movie> = serde_json::from_str(r#"{"title":"Interstellar"}"#).unwrap();
+        assert_eq!(hit.formatted_result, None);
+    }
+
+    #[test]
+    fn with_matches_is_encoded_in_the_url() {
+        let query = Query::new("space").with_matches(true);
+        assert!(query.to_url().contains("&matches=true"));
+    }
+
+    #[test]
+    fn search_result_deserializes_the_matches_info_field() {
+        let hit: SearchResult<Movie> = serde_json::from_str(
+            r#"{"title":"Interstellar","_matchesInfo":{"title":[{"start":0,"length":5}]}}"#,
+        )
+        .unwrap();
+        let matches_info = hit.matches_info.unwrap();
+        assert_eq!(
+            matches_info["title"],
+            vec![MatchRange {
+                start: 0,
+                length: 5
+            }]
+        );
+    }
+
+    #[test]
+    fn search_result_matches_info_is_none_when_absent() {
+        let hit: SearchResult<Movie> = serde_json::from_str(r#"{"title":"Interstellar"}"#).unwrap();
+        assert_eq!(hit.matches_info, None);
+    }
+
+    #[test]
+    fn page_and_hits_per_page_builders_are_encoded_in_the_url() {
+        let query = Query::new("space").with_page(3).with_hits_per_page(10);
+        let url = query.to_url();
+        assert!(url.contains("&page=3"));
+        assert!(url.contains("&hitsPerPage=10"));
+    }
+
+    #[test]
+    fn search_results_deserializes_page_based_pagination_fields() {
+        let json = r#"{
+            "hits": [],
+            "facetsDistribution": null,
+            "exhaustiveFacetsCount": null,
+            "processingTimeMs": 0,
+            "query": "space",
+            "page": 3,
+            "hitsPerPage": 10,
+            "totalPages": 5,
+            "totalHits": 42,
+            "estimatedTotalHits": 42
+        }"#;
+        let results: SearchResults<Movie> = serde_json::from_str(json).unwrap();
+        assert_eq!(results.offset, None);
+        assert_eq!(results.limit, None);
+        assert_eq!(results.nb_hits, None);
+        assert_eq!(results.page, Some(3));
+        assert_eq!(results.hits_per_page, Some(10));
+        assert_eq!(results.total_pages, Some(5));
+        assert_eq!(results.total_hits, Some(42));
+        assert_eq!(results.estimated_total_hits, Some(42));
+    }
+
+    #[test]
+    fn search_results_deserializes_offset_based_pagination_fields() {
+        let json = r#"{
+            "hits": [],
+            "offset": 0,
+            "limit": 20,
+            "nbHits": 42,
+            "exhaustiveNbHits": true,
+            "processingTimeMs": 0,
+            "query": "space"
+        }"#;
+        let results: SearchResults<Movie> = serde_json::from_str(json).unwrap();
+        assert_eq!(results.offset, Some(0));
+        assert_eq!(results.limit, Some(20));
+        assert_eq!(results.nb_hits, Some(42));
+        assert_eq!(results.exhaustive_nb_hits, Some(true));
+        assert_eq!(results.page, None);
+        assert_eq!(results.total_pages, None);
+    }
+
+    #[test]
+    fn query_is_serialized_under_the_q_key() {
+        let query = Query::new("space");
+        let json = serde_json::to_string(&query).unwrap();
+        assert!(json.contains(r#""q":"space""#));
+        assert!(!json.contains(r#""query":"#));
+    }
+
+    #[test]
+    fn facets_distribution_wildcard_is_serialized_as_a_star() {
+        let query = Query::new("space").with_facets_distribution(None);
+        let json = serde_json::to_string(&query).unwrap();
+        assert!(json.contains(r#""facetsDistribution":"*""#));
+    }
+
+    #[test]
+    fn facets_distribution_list_is_serialized_as_an_array() {
+        let query = Query::new("space").with_facets_distribution(Some(vec!["genre"]));
+        let json = serde_json::to_string(&query).unwrap();
+        assert!(json.contains(r#""facetsDistribution":["genre"]"#));
+    }
+
+    #[test]
+    fn with_sort_accepts_valid_rules() {
+        let query = Query::new("space")
+            .with_sort(&["price:asc", "release_date:desc"])
+            .unwrap();
+        assert_eq!(
+            query.sort,
+            Some(vec![
+                AscDesc::Asc("price".to_string()),
+                AscDesc::Desc("release_date".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn with_sort_rejects_a_malformed_rule() {
+        let err = Query::new("space").with_sort(&["price:up"]).unwrap_err();
+        assert!(matches!(err, Error::InvalidSortRule(rule) if rule == "price:up"));
+    }
+
+    #[test]
+    fn with_sort_rejects_a_rule_with_no_direction() {
+        let err = Query::new("space").with_sort(&["price"]).unwrap_err();
+        assert!(matches!(err, Error::InvalidSortRule(rule) if rule == "price"));
+    }
+
+    #[test]
+    fn matching_strategy_is_serialized_in_camel_case() {
+        assert_eq!(
+            serde_json::to_string(&MatchingStrategy::Last).unwrap(),
+            r#""last""#
+        );
+        assert_eq!(
+            serde_json::to_string(&MatchingStrategy::All).unwrap(),
+            r#""all""#
+        );
+    }
+
+    #[test]
+    fn with_matching_strategy_is_encoded_in_the_url() {
+        let query = Query::new("space").with_matching_strategy(MatchingStrategy::All);
+        assert!(query.to_url().contains("&matchingStrategy=all"));
+
+        let query = Query::new("space").with_matching_strategy(MatchingStrategy::Last);
+        assert!(query.to_url().contains("&matchingStrategy=last"));
+    }
 }